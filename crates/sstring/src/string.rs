@@ -5,31 +5,227 @@ use crate::SStringRef;
 
 type StdString = std::string::String;
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// A heap-allocated string backend that can be plugged into [`SStringBase`].
+///
+/// This trait is sealed: it is only implemented by the backends provided by this crate
+/// (`Box<str>`, `Arc<str>`, and `Rc<str>`).
+pub trait HeapStr: private::Sealed + Clone + fmt::Debug + Eq + Ord + std::hash::Hash {
+    /// Allocate a new backend value, copying `s`.
+    fn from_str(s: &str) -> Self;
+    /// Allocate a new backend value, taking ownership of `s`.
+    fn from_string(s: StdString) -> Self;
+    /// Allocate a new backend value, taking ownership of `s`.
+    fn from_boxed_str(s: Box<str>) -> Self;
+    /// Borrow the backend's contents as a `str`.
+    fn as_str(&self) -> &str;
+}
+
+impl private::Sealed for Box<str> {}
+
+impl HeapStr for Box<str> {
+    fn from_str(s: &str) -> Self {
+        s.into()
+    }
+
+    fn from_string(s: StdString) -> Self {
+        s.into_boxed_str()
+    }
+
+    fn from_boxed_str(s: Box<str>) -> Self {
+        s
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+impl private::Sealed for std::sync::Arc<str> {}
+
+/// Cheap, `O(1)`, thread-safe clones at the cost of an atomic refcount bump.
+impl HeapStr for std::sync::Arc<str> {
+    fn from_str(s: &str) -> Self {
+        s.into()
+    }
+
+    fn from_string(s: StdString) -> Self {
+        s.into()
+    }
+
+    fn from_boxed_str(s: Box<str>) -> Self {
+        s.into()
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+impl private::Sealed for std::rc::Rc<str> {}
+
+/// Cheap, `O(1)` clones for single-threaded use.
+impl HeapStr for std::rc::Rc<str> {
+    fn from_str(s: &str) -> Self {
+        s.into()
+    }
+
+    fn from_string(s: StdString) -> Self {
+        s.into()
+    }
+
+    fn from_boxed_str(s: Box<str>) -> Self {
+        s.into()
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+#[cfg(feature = "arc")]
+type DefaultStr = std::sync::Arc<str>;
+#[cfg(all(feature = "rc", not(feature = "arc")))]
+type DefaultStr = std::rc::Rc<str>;
+#[cfg(not(any(feature = "arc", feature = "rc")))]
+type DefaultStr = Box<str>;
+
 /// A UTF-8 encoded, immutable string.
+///
+/// Enable the `arc` or `rc` cargo features to make cloning an owned `SString` an `O(1)`
+/// operation, at the cost of going through `Arc<str>` or `Rc<str>` instead of `Box<str>`.
+pub type SString = SStringBase<DefaultStr>;
+
+/// A UTF-8 encoded, immutable string, generic over its heap-allocated backend `B`.
+///
+/// Most users should use the [`SString`] alias rather than naming this type directly.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(transparent)]
+#[serde(bound(serialize = "B: HeapStr", deserialize = "B: HeapStr"))]
 #[repr(transparent)]
-pub struct SString {
+pub struct SStringBase<B: HeapStr> {
     #[serde(with = "serde_string")]
-    pub(crate) inner: SStringInner,
+    pub(crate) inner: SStringInner<B>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub(crate) enum SStringInner {
-    Owned(StdString),
+pub(crate) enum SStringInner<B: HeapStr> {
+    Owned(B),
     Singleton(&'static str),
+    Inline(StackString),
+    // Behind an `Arc` rather than inline, so this variant's extra fields don't grow every
+    // `SStringInner` (including the far more common `Owned`/`Singleton`/`Inline` ones) past the
+    // 3-machine-word budget — and, unlike a `Box`, cloning an existing `Shared` value is a pure
+    // refcount bump rather than a fresh heap allocation.
+    Shared(std::sync::Arc<SharedInner>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct SharedInner {
+    buf: std::sync::Arc<str>,
+    start: usize,
+    end: usize,
 }
 
-impl SString {
-    /// Create a new empty `SString`.
+impl SharedInner {
+    #[inline]
+    fn as_str(&self) -> &str {
+        &self.buf[self.start..self.end]
+    }
+}
+
+/// Maximum length of a string that can be stored inline, without a heap allocation.
+///
+/// Chosen so `SStringInner` stays at or below 3 machine words (roughly 22 bytes on 64-bit).
+const STACK_STRING_CAPACITY: usize = 22;
+
+/// A short string stored inline on the stack, avoiding a heap allocation.
+#[derive(Clone, Copy, Eq)]
+pub(crate) struct StackString {
+    len: u8,
+    buf: [u8; STACK_STRING_CAPACITY],
+}
+
+impl StackString {
+    fn new(s: &str) -> Self {
+        debug_assert!(s.len() <= STACK_STRING_CAPACITY);
+        let mut buf = [0; STACK_STRING_CAPACITY];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        Self {
+            len: s.len() as u8,
+            buf,
+        }
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        // Safe because `buf[..len]` is only ever written with valid UTF-8 via `new`.
+        unsafe { std::str::from_utf8_unchecked(&self.buf[..self.len as usize]) }
+    }
+}
+
+impl fmt::Debug for StackString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for StackString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialOrd for StackString {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StackString {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl std::hash::Hash for StackString {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+static INTERNED: std::sync::Mutex<Option<std::collections::HashSet<&'static str>>> =
+    std::sync::Mutex::new(None);
+
+/// Canonicalize `s` to a single, process-lifetime `&'static str`.
+///
+/// On a cache miss, `s` is leaked (`Box::leak`) and the cache is populated; this is why
+/// [`SStringBase::interned`] should only be used for a bounded set of identifiers.
+fn intern(s: &str) -> &'static str {
+    let mut cache = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(std::collections::HashSet::new);
+    if let Some(existing) = cache.get(s) {
+        existing
+    } else {
+        let leaked: &'static str = Box::leak(s.into());
+        cache.insert(leaked);
+        leaked
+    }
+}
+
+impl<B: HeapStr> SStringBase<B> {
+    /// Create a new empty `SStringBase`.
     pub fn new() -> Self {
         Default::default()
     }
 
-    /// Create an owned `SString`.
+    /// Create an owned `SStringBase`.
     pub fn owned(other: impl Into<StdString>) -> Self {
         Self {
-            inner: SStringInner::Owned(other.into()),
+            inner: SStringInner::Owned(B::from_string(other.into())),
         }
     }
 
@@ -40,32 +236,136 @@ impl SString {
         }
     }
 
-    /// Get a reference to the `SString`.
+    /// Try to store `s` inline, without a heap allocation.
+    ///
+    /// Returns `None` if `s` is too long to fit in the inline buffer.
+    pub fn try_inline(s: &str) -> Option<Self> {
+        if s.len() <= STACK_STRING_CAPACITY {
+            Some(Self {
+                inner: SStringInner::Inline(StackString::new(s)),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create an `SStringBase` from a borrowed `str`, inlining short strings to avoid an
+    /// allocation.
+    pub fn from_ref(s: &str) -> Self {
+        Self::try_inline(s).unwrap_or_else(|| Self {
+            inner: SStringInner::Owned(B::from_str(s)),
+        })
+    }
+
+    /// Build an `SStringBase` from an `Arc<str>` the caller already owns.
+    ///
+    /// The result is `Shared`, so calling [`substr`](Self::substr) on it (or on any value
+    /// derived from it via `substr`) is genuinely `O(1)`: it slices the existing allocation
+    /// instead of copying it. Use this once on a full source buffer (a parsed template, a
+    /// tag body) before taking multiple spans out of it with `substr`.
+    pub fn from_arc(buf: std::sync::Arc<str>) -> Self {
+        let end = buf.len();
+        Self {
+            inner: SStringInner::Shared(std::sync::Arc::new(SharedInner { buf, start: 0, end })),
+        }
+    }
+
+    /// Extract an owned substring, sharing the same backing allocation as `self` when possible.
+    ///
+    /// `range` must fall on UTF-8 char boundaries, like slicing a `str` directly. If `self` is
+    /// already `Shared` (built via [`from_arc`](Self::from_arc) or a prior `substr` call), this
+    /// is `O(1)`: it reuses `self`'s backing `Arc<str>` (bumping its refcount) instead of
+    /// copying the string's bytes, though the result itself is a fresh, constant-size
+    /// allocation holding the new `start`/`end` — cloning that *result* afterwards is the part
+    /// that's allocation-free, a pure `Arc` refcount bump. When `self` isn't `Shared`, `substr`
+    /// is `O(n)`, since there is no existing `Arc<str>` to share and one must be allocated from
+    /// `self`'s full contents — for repeated slicing of one source buffer, build that buffer
+    /// once with `from_arc` instead of calling `substr` directly on a non-`Shared` base.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.start > range.end`, or if `range.start` or `range.end` does not lie on a
+    /// UTF-8 char boundary, or is out of bounds.
+    pub fn substr(&self, range: std::ops::Range<usize>) -> Self {
+        let s = self.as_str();
+        assert!(
+            range.start <= range.end,
+            "substr range must not start after it ends"
+        );
+        assert!(
+            s.is_char_boundary(range.start) && s.is_char_boundary(range.end),
+            "substr range must lie on UTF-8 char boundaries"
+        );
+        match self.inner {
+            SStringInner::Shared(ref shared) => Self {
+                inner: SStringInner::Shared(std::sync::Arc::new(SharedInner {
+                    buf: std::sync::Arc::clone(&shared.buf),
+                    start: shared.start + range.start,
+                    end: shared.start + range.end,
+                })),
+            },
+            _ => Self {
+                inner: SStringInner::Shared(std::sync::Arc::new(SharedInner {
+                    buf: std::sync::Arc::from(s),
+                    start: range.start,
+                    end: range.end,
+                })),
+            },
+        }
+    }
+
+    /// Pair `self` with a hash computed once now, rather than on every later `HashMap` lookup.
+    ///
+    /// See [`HashedSString`] for how the cached hash is used.
+    pub fn hashed(self) -> HashedSString<B> {
+        let hash = fast_hash(self.as_str());
+        HashedSString { s: self, hash }
+    }
+
+    /// Create an `SStringBase` from a canonicalized, process-lifetime copy of `s`.
+    ///
+    /// Repeated calls with equal strings return a value backed by the same `&'static str`
+    /// pointer, making later equality checks between interned values `O(1)`. Because interned
+    /// data is leaked for the life of the program, only use this for a bounded set of
+    /// identifiers (e.g. variable and filter names), not arbitrary user data.
+    pub fn interned(s: &str) -> Self {
+        Self {
+            inner: SStringInner::Singleton(intern(s)),
+        }
+    }
+
+    /// Get a reference to the `SStringBase`.
     pub fn as_ref(&self) -> SStringRef<'_> {
         match self.inner {
-            SStringInner::Owned(ref s) => SStringRef::borrow(s),
+            SStringInner::Owned(ref s) => SStringRef::borrow(s.as_str()),
             SStringInner::Singleton(ref s) => SStringRef::singleton(s),
+            SStringInner::Inline(ref s) => SStringRef::borrow(s.as_str()),
+            SStringInner::Shared(ref s) => SStringRef::borrow(s.as_str()),
         }
     }
 
-    /// Extracts a string slice containing the entire `SString`.
+    /// Extracts a string slice containing the entire `SStringBase`.
     pub fn as_str(&self) -> &str {
         match self.inner {
             SStringInner::Owned(ref s) => s.as_str(),
             SStringInner::Singleton(ref s) => s,
+            SStringInner::Inline(ref s) => s.as_str(),
+            SStringInner::Shared(ref s) => s.as_str(),
         }
     }
 
     /// Convert to a mutable string type, cloning the data if necessary.
     pub fn into_mut(self) -> StdString {
         match self.inner {
-            SStringInner::Owned(s) => s,
+            SStringInner::Owned(s) => s.as_str().to_owned(),
             SStringInner::Singleton(s) => s.to_owned(),
+            SStringInner::Inline(s) => s.as_str().to_owned(),
+            SStringInner::Shared(s) => s.as_str().to_owned(),
         }
     }
 }
 
-impl std::ops::Deref for SString {
+impl<B: HeapStr> std::ops::Deref for SStringBase<B> {
     type Target = str;
 
     #[inline]
@@ -74,95 +374,104 @@ impl std::ops::Deref for SString {
     }
 }
 
-impl Eq for SString {}
+impl<B: HeapStr> Eq for SStringBase<B> {}
 
-impl<'s> PartialEq<SString> for SString {
+impl<B: HeapStr> PartialEq<SStringBase<B>> for SStringBase<B> {
     #[inline]
-    fn eq(&self, other: &SString) -> bool {
+    fn eq(&self, other: &SStringBase<B>) -> bool {
+        // Interned strings are canonicalized, so identical pointers imply equal contents,
+        // letting the common interned-vs-interned comparison skip the byte comparison.
+        if let (SStringInner::Singleton(a), SStringInner::Singleton(b)) =
+            (&self.inner, &other.inner)
+        {
+            if std::ptr::eq(*a, *b) {
+                return true;
+            }
+        }
         PartialEq::eq(self.as_str(), other.as_str())
     }
 }
 
-impl<'s> PartialEq<str> for SString {
+impl<B: HeapStr> PartialEq<str> for SStringBase<B> {
     #[inline]
     fn eq(&self, other: &str) -> bool {
         PartialEq::eq(self.as_str(), other)
     }
 }
 
-impl<'s> PartialEq<&'s str> for SString {
+impl<'s, B: HeapStr> PartialEq<&'s str> for SStringBase<B> {
     #[inline]
     fn eq(&self, other: &&str) -> bool {
         PartialEq::eq(self.as_str(), *other)
     }
 }
 
-impl<'s> PartialEq<String> for SString {
+impl<B: HeapStr> PartialEq<String> for SStringBase<B> {
     #[inline]
     fn eq(&self, other: &StdString) -> bool {
         PartialEq::eq(self.as_str(), other.as_str())
     }
 }
 
-impl Ord for SString {
+impl<B: HeapStr> Ord for SStringBase<B> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.as_str().cmp(other.as_str())
     }
 }
 
-impl PartialOrd for SString {
+impl<B: HeapStr> PartialOrd for SStringBase<B> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.as_str().partial_cmp(other.as_str())
     }
 }
 
-impl std::hash::Hash for SString {
+impl<B: HeapStr> std::hash::Hash for SStringBase<B> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.as_str().hash(state);
     }
 }
 
-impl fmt::Display for SString {
+impl<B: HeapStr> fmt::Display for SStringBase<B> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(self.as_str(), f)
     }
 }
 
-impl AsRef<str> for SString {
+impl<B: HeapStr> AsRef<str> for SStringBase<B> {
     #[inline]
     fn as_ref(&self) -> &str {
         self.as_str()
     }
 }
 
-impl AsRef<[u8]> for SString {
+impl<B: HeapStr> AsRef<[u8]> for SStringBase<B> {
     #[inline]
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
     }
 }
 
-impl AsRef<std::ffi::OsStr> for SString {
+impl<B: HeapStr> AsRef<std::ffi::OsStr> for SStringBase<B> {
     fn as_ref(&self) -> &std::ffi::OsStr {
         (&**self).as_ref()
     }
 }
 
-impl AsRef<std::path::Path> for SString {
+impl<B: HeapStr> AsRef<std::path::Path> for SStringBase<B> {
     fn as_ref(&self) -> &std::path::Path {
         std::path::Path::new(self)
     }
 }
 
-impl std::borrow::Borrow<str> for SString {
+impl<B: HeapStr> std::borrow::Borrow<str> for SStringBase<B> {
     #[inline]
     fn borrow(&self) -> &str {
         self.as_str()
     }
 }
 
-impl Default for SString {
+impl<B: HeapStr> Default for SStringBase<B> {
     fn default() -> Self {
         "".into()
     }
@@ -192,17 +501,20 @@ impl<'s> From<&'s SStringCow<'s>> for SString {
     }
 }
 
-impl From<StdString> for SString {
+impl<B: HeapStr> From<StdString> for SStringBase<B> {
     fn from(other: StdString) -> Self {
-        SString {
-            inner: SStringInner::Owned(other),
-        }
+        Self::try_inline(&other).unwrap_or_else(|| Self {
+            inner: SStringInner::Owned(B::from_string(other)),
+        })
     }
 }
 
-impl From<&'static str> for SString {
+impl<B: HeapStr> From<&'static str> for SStringBase<B> {
     fn from(other: &'static str) -> Self {
-        SString {
+        // `other` is already a free, zero-copy `'static` pointer, so unlike the owned-data
+        // paths (`From<String>`, `from_ref`) there is no allocation to dodge by inlining it —
+        // doing so would instead replace a pointer store with an unconditional buffer copy.
+        Self {
             inner: SStringInner::Singleton(other),
         }
     }
@@ -212,22 +524,278 @@ mod serde_string {
     use super::*;
     use serde::{self, Deserialize, Deserializer, Serializer};
 
-    pub(crate) fn serialize<S>(data: &SStringInner, serializer: S) -> Result<S::Ok, S::Error>
+    pub(crate) fn serialize<B, S>(data: &SStringInner<B>, serializer: S) -> Result<S::Ok, S::Error>
     where
+        B: HeapStr,
         S: Serializer,
     {
         let s = match data {
             SStringInner::Owned(ref s) => s.as_str(),
             SStringInner::Singleton(ref s) => s,
+            SStringInner::Inline(ref s) => s.as_str(),
+            SStringInner::Shared(ref s) => s.as_str(),
         };
-        serializer.serialize_str(&s)
+        serializer.serialize_str(s)
     }
 
-    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<SStringInner, D::Error>
+    pub(crate) fn deserialize<'de, B, D>(deserializer: D) -> Result<SStringInner<B>, D::Error>
     where
+        B: HeapStr,
         D: Deserializer<'de>,
     {
         let s = StdString::deserialize(deserializer)?;
-        Ok(SStringInner::Owned(s))
+        Ok(SStringInner::Owned(B::from_string(s)))
+    }
+}
+
+/// Compute a string hash once, at [`SStringBase::hashed`] construction time.
+///
+/// This uses the standard library's `DefaultHasher` (SipHash), not a dedicated fast hasher, so
+/// there is no speedup in the hashing itself versus the default byte-based `Hash` impl. The win
+/// is purely from amortization: a [`HashedSString`] built once and reused across many `HashMap`
+/// probes (e.g. the same scope key looked up on every variable reference) pays this cost once
+/// instead of re-walking the bytes on every lookup.
+fn fast_hash(s: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// An [`SStringBase`] paired with a hash computed once at construction, for use as a hot-path
+/// `HashMap` key (e.g. resolving variables in an object scope).
+///
+/// Construct with [`SStringBase::hashed`]. Pair the key with [`StraightHasherBuilder`] so map
+/// probes feed the cached hash straight to the `Hasher` instead of re-walking the string bytes
+/// on every lookup. Equality still falls back to a byte comparison, so hash collisions remain
+/// safe; this is strictly an opt-in fast path for hot scope maps, not a replacement for the
+/// default byte-based [`Hash`](std::hash::Hash) impl on [`SStringBase`], which interop code
+/// still relies on.
+///
+/// There is deliberately no `Borrow<str>` impl: [`StraightHasher`] only understands a
+/// precomputed `write_u64` and panics on the byte-wise `write` a plain `&str` key would go
+/// through, so a `HashMap<HashedSString<B>, V, StraightHasherBuilder>` cannot be probed with a
+/// bare `&str` regardless. To look one up, wrap the query key the same way with
+/// [`SStringBase::hashed`] (which does hash the query string) before probing the map.
+#[derive(Clone, Debug)]
+pub struct HashedSString<B: HeapStr = DefaultStr> {
+    s: SStringBase<B>,
+    hash: u64,
+}
+
+impl<B: HeapStr> std::ops::Deref for HashedSString<B> {
+    type Target = SStringBase<B>;
+
+    #[inline]
+    fn deref(&self) -> &SStringBase<B> {
+        &self.s
+    }
+}
+
+impl<B: HeapStr> PartialEq for HashedSString<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.s == other.s
+    }
+}
+
+impl<B: HeapStr> Eq for HashedSString<B> {}
+
+impl<B: HeapStr> std::hash::Hash for HashedSString<B> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// A [`std::hash::BuildHasher`] for keys that already carry a precomputed hash (such as
+/// [`HashedSString`]), feeding it straight through rather than hashing bytes.
+#[derive(Clone, Copy, Default)]
+pub struct StraightHasherBuilder;
+
+impl std::hash::BuildHasher for StraightHasherBuilder {
+    type Hasher = StraightHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        StraightHasher(0)
+    }
+}
+
+/// A [`std::hash::Hasher`] that only understands a single precomputed `u64`, written via
+/// `write_u64`, and returns it verbatim from `finish`.
+pub struct StraightHasher(u64);
+
+impl std::hash::Hasher for StraightHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {
+        unreachable!("StraightHasher only supports pre-hashed keys; see HashedSString")
     }
-}
\ No newline at end of file
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = i;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_backend_arc_owned_and_clone_round_trip() {
+        type ArcString = SStringBase<std::sync::Arc<str>>;
+
+        let s: ArcString = ArcString::owned("a string long enough to need the heap backend");
+        assert_eq!(s.as_str(), "a string long enough to need the heap backend");
+
+        // `Arc<str>` makes clone `O(1)`, but it must still be byte-equal and independently
+        // usable, exercising the `HeapStr` plumbing rather than just its type-checking.
+        let cloned = s.clone();
+        assert_eq!(s, cloned);
+        assert_eq!(cloned.into_mut(), s.as_str());
+    }
+
+    #[test]
+    fn generic_backend_rc_owned_and_clone_round_trip() {
+        type RcString = SStringBase<std::rc::Rc<str>>;
+
+        let s: RcString = RcString::owned("a string long enough to need the heap backend");
+        assert_eq!(s.as_str(), "a string long enough to need the heap backend");
+
+        let cloned = s.clone();
+        assert_eq!(s, cloned);
+        assert_eq!(cloned.into_mut(), s.as_str());
+    }
+
+    #[test]
+    fn generic_backend_serde_round_trip_for_arc_and_rc() {
+        type ArcString = SStringBase<std::sync::Arc<str>>;
+        type RcString = SStringBase<std::rc::Rc<str>>;
+
+        let arc: ArcString = ArcString::owned("arc backend serde round trip");
+        let json = serde_json::to_string(&arc).unwrap();
+        assert_eq!(json, "\"arc backend serde round trip\"");
+        let deserialized: ArcString = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, arc);
+
+        let rc: RcString = RcString::owned("rc backend serde round trip");
+        let json = serde_json::to_string(&rc).unwrap();
+        let deserialized: RcString = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, rc);
+    }
+
+    #[test]
+    fn default_backend_matches_the_feature_selected_type() {
+        // Confirms `DefaultStr`'s feature-based `cfg` selection actually resolves to a type
+        // `SString` can be built from, under whichever of `arc`/`rc`/neither is enabled.
+        let s: SString = SString::owned("default backend selection");
+        assert_eq!(s.as_str(), "default backend selection");
+
+        // Each binding only compiles if `DefaultStr` is exactly the backend the enabled
+        // features select.
+        #[cfg(feature = "arc")]
+        let _: std::sync::Arc<str> = <DefaultStr as HeapStr>::from_str("");
+        #[cfg(all(feature = "rc", not(feature = "arc")))]
+        let _: std::rc::Rc<str> = <DefaultStr as HeapStr>::from_str("");
+        #[cfg(not(any(feature = "arc", feature = "rc")))]
+        let _: Box<str> = <DefaultStr as HeapStr>::from_str("");
+    }
+
+    #[test]
+    fn try_inline_fits_at_capacity() {
+        let s = "a".repeat(STACK_STRING_CAPACITY);
+        let inlined = SString::try_inline(&s).unwrap();
+        assert!(matches!(inlined.inner, SStringInner::Inline(_)));
+        assert_eq!(inlined.as_str(), s);
+    }
+
+    #[test]
+    fn try_inline_rejects_one_byte_over_capacity() {
+        let s = "a".repeat(STACK_STRING_CAPACITY + 1);
+        assert!(SString::try_inline(&s).is_none());
+    }
+
+    #[test]
+    fn from_ref_inlines_short_strings_and_allocates_long_ones() {
+        let short = "a".repeat(STACK_STRING_CAPACITY - 1);
+        let long = "a".repeat(STACK_STRING_CAPACITY + 1);
+
+        let short = SString::from_ref(&short);
+        let long = SString::from_ref(&long);
+
+        assert!(matches!(short.inner, SStringInner::Inline(_)));
+        assert!(matches!(long.inner, SStringInner::Owned(_)));
+    }
+
+    #[test]
+    fn interned_canonicalizes_to_the_same_pointer() {
+        // Build the two inputs from distinct allocations so a naive byte comparison and a
+        // pointer comparison could disagree if interning wasn't actually canonicalizing them.
+        // The `to_owned()` calls are deliberate, not redundant, despite what
+        // `clippy::unnecessary_to_owned` would otherwise conclude from the immediate `&`.
+        #[allow(clippy::unnecessary_to_owned)]
+        let a = SString::interned(&"distinct-identifier".to_owned());
+        #[allow(clippy::unnecessary_to_owned)]
+        let b = SString::interned(&"distinct-identifier".to_owned());
+
+        let (SStringInner::Singleton(pa), SStringInner::Singleton(pb)) = (&a.inner, &b.inner)
+        else {
+            panic!("interned() must produce a Singleton variant");
+        };
+        assert!(std::ptr::eq(*pa, *pb));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn substr_on_shared_base_shares_the_allocation() {
+        let source: std::sync::Arc<str> = std::sync::Arc::from("hello world");
+        let base = SString::from_arc(std::sync::Arc::clone(&source));
+
+        let hello = base.substr(0..5);
+        let world = base.substr(6..11);
+
+        assert_eq!(hello, "hello");
+        assert_eq!(world, "world");
+        let (SStringInner::Shared(hello), SStringInner::Shared(world)) =
+            (&hello.inner, &world.inner)
+        else {
+            panic!("substr() on a Shared base must stay Shared");
+        };
+        assert!(std::sync::Arc::ptr_eq(&hello.buf, &source));
+        assert!(std::sync::Arc::ptr_eq(&world.buf, &source));
+    }
+
+    #[test]
+    fn substr_on_non_shared_base_still_materializes_shared() {
+        let base = SString::owned("hello world");
+        let hello = base.substr(0..5);
+        assert_eq!(hello, "hello");
+        assert!(matches!(hello.inner, SStringInner::Shared(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not start after it ends")]
+    fn substr_panics_when_start_is_after_end() {
+        let base = SString::owned("hello world");
+        // Build the bounds from variables, not a range literal, so clippy's
+        // `reversed_empty_ranges` lint (which would fire on a literal `5..3`) doesn't trip on a
+        // reversed range that's deliberately under test.
+        let (start, end) = (5, 3);
+        // Both 5 and 3 are valid char boundaries in this ASCII string, so only the explicit
+        // start<=end check (not the char-boundary check) can catch this.
+        let _ = base.substr(start..end);
+    }
+
+    #[test]
+    fn hashed_sstring_round_trips_through_a_straight_hashed_map() {
+        let mut map: std::collections::HashMap<HashedSString, i32, StraightHasherBuilder> =
+            std::collections::HashMap::default();
+
+        map.insert(SString::owned("alpha").hashed(), 1);
+        map.insert(SString::owned("beta").hashed(), 2);
+
+        assert_eq!(map.get(&SString::owned("alpha").hashed()), Some(&1));
+        assert_eq!(map.get(&SString::owned("beta").hashed()), Some(&2));
+        assert_eq!(map.get(&SString::owned("gamma").hashed()), None);
+    }
+}